@@ -18,8 +18,8 @@
 
 use std::cmp;
 use std::io::Result;
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use fuse_backend_rs::file_buf::FileVolatileSlice;
 use nydus_utils::{compress, digest};
@@ -47,6 +47,146 @@ pub use fscache::FsCacheMgr;
 /// Timeout in milli-seconds to retrieve blob data from backend storage.
 pub const SINGLE_INFLIGHT_WAIT_TIMEOUT: u64 = 2000;
 
+/// Minimum number of chunks in a single backend read batch before decompression and digest
+/// validation are parallelized across worker threads.
+const PARALLEL_DECOMPRESS_CHUNK_THRESHOLD: usize = 4;
+
+/// Minimum total uncompressed size (bytes) of a single backend read batch before decompression
+/// and digest validation are parallelized across worker threads.
+const PARALLEL_DECOMPRESS_SIZE_THRESHOLD: u64 = 1024 * 1024;
+
+/// Maximum number of worker threads used to decompress and validate the chunk spans of a single
+/// backend read batch in parallel. Bounded independently of the batch size, so an oversized
+/// amplified or prefetch read can't spawn one OS thread per chunk.
+const MAX_DECOMPRESS_WORKERS: usize = 4;
+
+/// Decide whether a batch of the given chunk count/total uncompressed size is worth
+/// parallelizing decompression and digest validation for, versus the thread-dispatch overhead
+/// of doing so on a single small chunk.
+fn should_parallelize_decompress(chunk_count: usize, total_d_size: u64) -> bool {
+    chunk_count >= PARALLEL_DECOMPRESS_CHUNK_THRESHOLD
+        || total_d_size >= PARALLEL_DECOMPRESS_SIZE_THRESHOLD
+}
+
+/// Policy for retrying a transient storage backend read failure (error or short read) with
+/// exponential backoff.
+///
+/// All fields default to zero, which preserves the original behavior of failing fast with
+/// `eio!` on the first error or short read. Retries only ever cover the network read itself;
+/// a read that succeeds but later fails digest validation is never retried, since that
+/// indicates data corruption rather than a transient backend fault.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BackendRetryPolicy {
+    /// Maximum number of retries after the initial attempt.
+    pub max_retries: u8,
+    /// Base backoff delay in milliseconds, doubled after each retry.
+    pub base_delay_ms: u64,
+    /// Upper bound on the backoff delay in milliseconds.
+    pub max_delay_ms: u64,
+}
+
+/// Backoff delay to use before the first retry, clamped to `max_delay_ms` up front so a
+/// misconfigured `base_delay_ms > max_delay_ms` can't violate the documented upper bound on the
+/// very first retry.
+fn initial_backoff_delay(policy: &BackendRetryPolicy) -> u64 {
+    cmp::min(policy.base_delay_ms, policy.max_delay_ms)
+}
+
+/// Backoff delay to use for the retry after this one: double `delay_ms`, capped at
+/// `max_delay_ms`.
+fn next_backoff_delay(delay_ms: u64, max_delay_ms: u64) -> u64 {
+    cmp::min(delay_ms.saturating_mul(2), max_delay_ms)
+}
+
+/// Sleep duration for this retry attempt: `delay_ms` plus a small jitter spread across the
+/// delay window (to avoid retries from concurrent readers lining up in lockstep), clamped to
+/// `max_delay_ms` so the documented upper bound holds for the jittered sleep actually performed,
+/// not just for the un-jittered `delay_ms`.
+fn backoff_sleep_ms(delay_ms: u64, attempt: u8, max_delay_ms: u64) -> u64 {
+    let jitter_ms = delay_ms / 10 + (attempt as u64 * 7) % (delay_ms / 4 + 1);
+    cmp::min(delay_ms.saturating_add(jitter_ms), max_delay_ms)
+}
+
+/// A token-bucket bandwidth limiter, used to throttle background prefetch reads so they don't
+/// starve foreground user IO of network bandwidth.
+///
+/// The bucket holds up to `burst` bytes and is refilled at `rate` bytes/sec based on elapsed
+/// time on each `acquire()` call. A `rate` of zero makes the limiter a no-op.
+pub struct BandwidthLimiter {
+    rate: u64,
+    burst: u64,
+    state: Mutex<BandwidthLimiterState>,
+}
+
+struct BandwidthLimiterState {
+    tokens: u64,
+    last_check: Instant,
+}
+
+impl BandwidthLimiter {
+    /// Create a new bandwidth limiter with the given `rate` (bytes/sec) and `burst` (bytes).
+    pub fn new(rate: u64, burst: u64) -> Self {
+        BandwidthLimiter {
+            rate,
+            burst,
+            state: Mutex::new(BandwidthLimiterState {
+                tokens: burst,
+                last_check: Instant::now(),
+            }),
+        }
+    }
+
+    /// Acquire `size` bytes worth of tokens, blocking the calling thread until enough tokens
+    /// have been refilled. A single request bigger than `burst` is allowed to pass once the
+    /// bucket is full, to avoid deadlocking on an oversized chunk.
+    pub fn acquire(&self, size: u64) {
+        if self.rate == 0 {
+            return;
+        }
+
+        if self.burst == 0 {
+            // A zero-capacity bucket can never hold tokens, so `state.tokens >= self.burst`
+            // below would always be true and let every request through unthrottled. Special-
+            // case it as a strict rate limiter instead: always wait out the time needed to
+            // "earn" `size` bytes at `rate` bytes/sec.
+            let wait = Duration::from_secs_f64(size as f64 / self.rate as f64);
+            if !wait.is_zero() {
+                std::thread::sleep(wait);
+            }
+            return;
+        }
+
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.last_check).as_secs_f64();
+                state.last_check = now;
+                let refilled = (elapsed * self.rate as f64) as u64;
+                state.tokens = cmp::min(self.burst, state.tokens.saturating_add(refilled));
+
+                if size <= state.tokens {
+                    state.tokens -= size;
+                    None
+                } else if state.tokens >= self.burst {
+                    // The bucket is already full but the request still exceeds its capacity.
+                    // Let it through once instead of blocking forever.
+                    state.tokens = 0;
+                    None
+                } else {
+                    let needed = size - state.tokens;
+                    Some(Duration::from_secs_f64(needed as f64 / self.rate as f64))
+                }
+            };
+
+            match wait {
+                Some(delay) => std::thread::sleep(delay),
+                None => break,
+            }
+        }
+    }
+}
+
 struct BlobIoMergeState<'a, F: FnMut(BlobIoRange)> {
     cb: F,
     // size of compressed data
@@ -160,6 +300,58 @@ pub trait BlobCache: Send + Sync {
     /// Get the [BlobReader](../backend/trait.BlobReader.html) to read data from storage backend.
     fn reader(&self) -> &dyn BlobReader;
 
+    /// Get the retry policy applied to transient storage backend read failures.
+    fn retry_policy(&self) -> BackendRetryPolicy {
+        BackendRetryPolicy::default()
+    }
+
+    /// Read `buf.len()` bytes at `offset` from the storage backend into `buf`, retrying
+    /// transient failures (errors or short reads) according to `retry_policy()` with
+    /// exponential backoff and a small jitter. Returns `eio!` once retries are exhausted.
+    fn read_backend_with_retry(&self, buf: &mut [u8], offset: u64) -> Result<usize> {
+        let policy = self.retry_policy();
+        let mut attempt = 0u8;
+        let mut delay_ms = initial_backoff_delay(&policy);
+
+        loop {
+            let result = self
+                .reader()
+                .read(buf, offset)
+                .map_err(|e| eio!(e))
+                .and_then(|n| {
+                    if n != buf.len() {
+                        Err(eio!(format!(
+                            "storage backend returns less data than requested: {} of {} bytes",
+                            n,
+                            buf.len()
+                        )))
+                    } else {
+                        Ok(n)
+                    }
+                });
+
+            match result {
+                Ok(n) => return Ok(n),
+                Err(e) if attempt < policy.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "failed to read {} bytes at offset {:#x} from blob {}, retrying attempt {}/{}: {}",
+                        buf.len(),
+                        offset,
+                        self.blob_id(),
+                        attempt,
+                        policy.max_retries,
+                        e
+                    );
+                    let sleep_ms = backoff_sleep_ms(delay_ms, attempt, policy.max_delay_ms);
+                    std::thread::sleep(Duration::from_millis(sleep_ms));
+                    delay_ms = next_backoff_delay(delay_ms, policy.max_delay_ms);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     /// Get the underlying `ChunkMap` object.
     fn get_chunk_map(&self) -> &Arc<dyn ChunkMap>;
 
@@ -198,8 +390,78 @@ pub trait BlobCache: Send + Sync {
     }
 
     /// Read chunk data described by the blob Io descriptors from the blob cache into the buffer.
+    ///
+    /// Implementations should merge the requested chunks with `BlobIoMergeState` and may call
+    /// `amplify_io_range()` on the merged range before fetching, to opportunistically warm the
+    /// local cache with chunks adjacent to the requested range.
     fn read(&self, iovec: &mut BlobIoVec, buffers: &[FileVolatileSlice]) -> Result<usize>;
 
+    /// Get the batch size in bytes used to amplify on-demand user IO requests.
+    ///
+    /// When non-zero, `amplify_io_range()` may pull in additional chunks that are physically
+    /// contiguous with the requested range in compressed space, up to this many bytes, so that
+    /// later sequential reads are served from the local cache instead of the backend. A value of
+    /// zero disables amplification, preserving the original on-demand behavior. This is distinct
+    /// from the batch size used for background prefetch.
+    fn user_io_batch_size(&self) -> u64 {
+        0
+    }
+
+    /// Amplify a merged user IO range with additional chunks contiguous in compressed space.
+    ///
+    /// Given the blob offset/size and chunk list covering a just-merged user IO request, walk
+    /// forward through the blob's chunk table appending chunks while they remain strictly
+    /// contiguous with the running end offset (see `BlobIoDesc::is_continuous`) and the
+    /// accumulated compressed size stays within `user_io_batch_size()`. The caller must still
+    /// only copy bytes belonging to the original `chunks` into its buffers; the extra chunks
+    /// returned here exist solely to be fetched and inserted into the `ChunkMap`.
+    fn amplify_io_range(
+        &self,
+        blob_offset: u64,
+        blob_size: usize,
+        chunks: &[Arc<dyn BlobChunkInfo>],
+    ) -> (u64, usize, Vec<Arc<dyn BlobChunkInfo>>) {
+        let batch_size = self.user_io_batch_size();
+        if batch_size == 0 || chunks.is_empty() {
+            return (blob_offset, blob_size, chunks.to_vec());
+        }
+
+        let mut amplified = chunks.to_vec();
+        let last = &chunks[chunks.len() - 1];
+        let mut end_offset = last.compressed_offset() + last.compressed_size() as u64;
+        let mut index = last.id() + 1;
+        let mut appended = false;
+
+        while end_offset - blob_offset < batch_size {
+            let next = match self.get_chunk_info(index) {
+                Some(next) => next,
+                None => break,
+            };
+            if next.compressed_offset() != end_offset {
+                break;
+            }
+            let next_end = end_offset + next.compressed_size() as u64;
+            if next.uncompressed_size() as u64 > RAFS_MAX_CHUNK_SIZE
+                || next_end - blob_offset > batch_size
+            {
+                break;
+            }
+            end_offset = next_end;
+            amplified.push(next);
+            index += 1;
+            appended = true;
+        }
+
+        if appended {
+            (blob_offset, (end_offset - blob_offset) as usize, amplified)
+        } else {
+            // Nothing was amplified in, so return the caller's own `blob_size` unchanged
+            // instead of recomputing it from the last chunk's end offset, which would diverge
+            // if the merged range isn't exactly chunk-aligned.
+            (blob_offset, blob_size, amplified)
+        }
+    }
+
     /// Read multiple chunks from the blob cache in batch mode.
     ///
     /// This is an interface to optimize chunk data fetch performance by merging multiple continuous
@@ -209,6 +471,12 @@ pub trait BlobCache: Send + Sync {
     /// for each entry in the `chunks` array in corresponding order.
     ///
     /// This method returns success only if all requested data are successfully fetched.
+    ///
+    /// For on-demand user IO (`prefetch == false`), the requested `chunks` may be amplified
+    /// with additional chunks contiguous in compressed space (see `amplify_io_range()`): only
+    /// the first `chunks.len()` entries of the returned buffer vector correspond to the
+    /// caller's original request, any further entries exist solely to warm the local cache and
+    /// should still be inserted into the `ChunkMap` by the caller.
     fn read_chunks_from_backend(
         &self,
         blob_offset: u64,
@@ -216,19 +484,58 @@ pub trait BlobCache: Send + Sync {
         chunks: &[Arc<dyn BlobChunkInfo>],
         prefetch: bool,
     ) -> Result<(Vec<Vec<u8>>, Vec<u8>)> {
+        self.read_chunks_from_backend_with_limit(
+            blob_offset,
+            blob_size,
+            chunks,
+            prefetch,
+            self.prefetch_limiter(),
+        )
+    }
+
+    /// Get the bandwidth limiter applied to this cache's background prefetch reads, if the
+    /// owning `BlobCacheMgr` has one configured.
+    ///
+    /// Concrete `BlobCache` implementations should override this to return the shared
+    /// `Arc<BandwidthLimiter>` obtained from `BlobCacheMgr::prefetch_limiter()` on
+    /// construction, so that every blob managed by the same manager throttles against the same
+    /// bucket. With the default `None`, `read_chunks_from_backend()` never throttles, same as
+    /// before this limiter existed.
+    fn prefetch_limiter(&self) -> Option<&Arc<BandwidthLimiter>> {
+        None
+    }
+
+    /// Same as `read_chunks_from_backend()`, but additionally consults `limiter` to throttle
+    /// the backend read when `prefetch` is true. User IO reads (`prefetch == false`) are never
+    /// throttled, regardless of `limiter`.
+    fn read_chunks_from_backend_with_limit(
+        &self,
+        blob_offset: u64,
+        blob_size: usize,
+        chunks: &[Arc<dyn BlobChunkInfo>],
+        prefetch: bool,
+        limiter: Option<&Arc<BandwidthLimiter>>,
+    ) -> Result<(Vec<Vec<u8>>, Vec<u8>)> {
+        if prefetch {
+            if let Some(limiter) = limiter {
+                limiter.acquire(blob_size as u64);
+            }
+        }
+
+        // Opportunistically amplify on-demand user IO with chunks contiguous in compressed
+        // space, so that later sequential reads hit the local cache instead of the backend.
+        // Prefetch reads already cover whatever range the prefetch request asked for, so they
+        // are left untouched.
+        let (blob_offset, blob_size, amplified_chunks) = if prefetch {
+            (blob_offset, blob_size, chunks.to_vec())
+        } else {
+            self.amplify_io_range(blob_offset, blob_size, chunks)
+        };
+
         // Read requested data from the backend by altogether.
         let mut c_buf = alloc_buf(blob_size);
         let start = Instant::now();
-        let nr_read = self
-            .reader()
-            .read(c_buf.as_mut_slice(), blob_offset)
-            .map_err(|e| eio!(e))?;
-        if nr_read != blob_size {
-            return Err(eio!(format!(
-                "request for {} bytes but got {} bytes",
-                blob_size, nr_read
-            )));
-        }
+        self.read_backend_with_retry(c_buf.as_mut_slice(), blob_offset)?;
         let duration = Instant::now().duration_since(start).as_millis();
         debug!(
             "read_chunks_from_backend: {} {} {} bytes at {}, duration {}ms",
@@ -239,7 +546,7 @@ pub trait BlobCache: Send + Sync {
             duration
         );
 
-        self.decompress_normal_chunks(blob_offset, chunks, c_buf)
+        self.decompress_normal_chunks(blob_offset, &amplified_chunks, c_buf)
     }
 
     /// Read a whole chunk directly from the storage backend.
@@ -252,6 +559,18 @@ pub trait BlobCache: Send + Sync {
         buffer: &mut [u8],
         force_validation: bool,
     ) -> Result<Option<Vec<u8>>> {
+        // Content-addressed chunks may already be cached under a different blob, e.g. a layer
+        // that shares chunk content with another image layer. Only trust the dedup cache when
+        // digests are validated, since that's what makes the shared content trustworthy.
+        if self.need_validate() {
+            if let Some(dedup) = self.dedup_cache() {
+                if let Some(cached) = dedup.get(self.blob_id(), chunk.chunk_id()) {
+                    buffer.copy_from_slice(&cached);
+                    return Ok(None);
+                }
+            }
+        }
+
         let offset = chunk.compressed_offset();
 
         let mut c_buf = None;
@@ -262,34 +581,43 @@ pub trait BlobCache: Send + Sync {
                 chunk.compressed_size() as usize
             };
             let mut raw_buffer = alloc_buf(c_size);
-            let size = self
-                .reader()
-                .read(raw_buffer.as_mut_slice(), offset)
-                .map_err(|e| eio!(e))?;
-            if size != raw_buffer.len() {
-                return Err(eio!("storage backend returns less data than requested"));
-            }
+            self.read_backend_with_retry(raw_buffer.as_mut_slice(), offset)?;
             self.decompress_chunk_data(&raw_buffer, buffer, true)?;
             c_buf = Some(raw_buffer);
         } else {
-            let size = self.reader().read(buffer, offset).map_err(|e| eio!(e))?;
-            if size != buffer.len() {
-                return Err(eio!("storage backend returns less data than requested"));
-            }
+            self.read_backend_with_retry(buffer, offset)?;
         }
 
         self.validate_chunk_data(chunk, buffer, force_validation)?;
 
+        if self.need_validate() {
+            if let Some(dedup) = self.dedup_cache() {
+                dedup.insert(self.blob_id(), chunk.chunk_id().clone(), buffer.to_vec());
+            }
+        }
+
         Ok(c_buf)
     }
 
+    /// Get the shared, content-addressed chunk dedup cache of the owning `BlobCacheMgr`, if
+    /// cross-blob chunk deduplication is enabled.
+    ///
+    /// Concrete `BlobCache` implementations should override this to return the shared dedup
+    /// cache obtained from `BlobCacheMgr::dedup_cache()` on construction, so that every blob
+    /// managed by the same manager dedups against the same cache.
+    fn dedup_cache(&self) -> Option<&dyn BlobChunkDedupCache> {
+        None
+    }
+
     fn decompress_normal_chunks(
         &self,
         blob_offset: u64,
         chunks: &[Arc<dyn BlobChunkInfo>],
         c_buf: Vec<u8>,
     ) -> Result<(Vec<Vec<u8>>, Vec<u8>)> {
-        let mut buffers: Vec<Vec<u8>> = Vec::with_capacity(chunks.len());
+        let mut spans: Vec<(&Arc<dyn BlobChunkInfo>, usize, usize)> =
+            Vec::with_capacity(chunks.len());
+        let mut total_d_size = 0u64;
         for chunk in chunks {
             let offset = chunk.compressed_offset();
             let size = chunk.compressed_size();
@@ -308,16 +636,114 @@ pub trait BlobCache: Send + Sync {
 
             let offset_merged = (offset - blob_offset) as usize;
             let end_merged = offset_merged + size as usize;
-            let buf = &c_buf[offset_merged..end_merged];
-            let mut buffer = alloc_buf(d_size);
-            self.decompress_chunk_data(buf, &mut buffer, chunk.is_compressed())?;
-            self.validate_chunk_data(chunk.as_ref(), &buffer, self.need_validate())?;
-            buffers.push(buffer);
+            total_d_size += d_size as u64;
+            spans.push((chunk, offset_merged, end_merged));
         }
 
+        let buffers = if should_parallelize_decompress(spans.len(), total_d_size) {
+            self.decompress_spans_parallel(&spans, &c_buf)?
+        } else {
+            self.decompress_spans_serial(&spans, &c_buf)?
+        };
+
         Ok((buffers, c_buf))
     }
 
+    /// Decompress and validate a single chunk span, consulting the shared dedup cache first.
+    ///
+    /// Content-addressed chunks may already be cached under a different blob, e.g. a layer that
+    /// shares chunk content with another image layer (see `read_chunk_from_backend`). Only trust
+    /// the dedup cache when digests are validated, since that's what makes the shared content
+    /// trustworthy.
+    fn decompress_span_with_dedup(
+        &self,
+        chunk: &Arc<dyn BlobChunkInfo>,
+        offset_merged: usize,
+        end_merged: usize,
+        c_buf: &[u8],
+        dedup: Option<&dyn BlobChunkDedupCache>,
+    ) -> Result<Vec<u8>> {
+        if let Some(dedup) = dedup {
+            if let Some(cached) = dedup.get(self.blob_id(), chunk.chunk_id()) {
+                return Ok((*cached).clone());
+            }
+        }
+
+        let d_size = chunk.uncompressed_size() as usize;
+        let buf = &c_buf[offset_merged..end_merged];
+        let mut buffer = alloc_buf(d_size);
+        self.decompress_chunk_data(buf, &mut buffer, chunk.is_compressed())?;
+        self.validate_chunk_data(chunk.as_ref(), &buffer, self.need_validate())?;
+
+        if let Some(dedup) = dedup {
+            dedup.insert(self.blob_id(), chunk.chunk_id().clone(), buffer.clone());
+        }
+
+        Ok(buffer)
+    }
+
+    /// Decompress and validate each chunk span serially, in order.
+    fn decompress_spans_serial(
+        &self,
+        spans: &[(&Arc<dyn BlobChunkInfo>, usize, usize)],
+        c_buf: &[u8],
+    ) -> Result<Vec<Vec<u8>>> {
+        let dedup = self.dedup_cache().filter(|_| self.need_validate());
+        let mut buffers: Vec<Vec<u8>> = Vec::with_capacity(spans.len());
+        for &(chunk, offset_merged, end_merged) in spans {
+            let buffer =
+                self.decompress_span_with_dedup(chunk, offset_merged, end_merged, c_buf, dedup)?;
+            buffers.push(buffer);
+        }
+        Ok(buffers)
+    }
+
+    /// Decompress and validate each chunk span in parallel, using a bounded pool of worker
+    /// threads rather than one thread per chunk.
+    ///
+    /// Each chunk's compressed slice and output buffer are independent, so this is
+    /// embarrassingly parallel. Results are reassembled in the original order, and the first
+    /// failing chunk (by original order) aborts the whole read, matching the serial semantics.
+    fn decompress_spans_parallel(
+        &self,
+        spans: &[(&Arc<dyn BlobChunkInfo>, usize, usize)],
+        c_buf: &[u8],
+    ) -> Result<Vec<Vec<u8>>> {
+        let dedup = self.dedup_cache().filter(|_| self.need_validate());
+        let pool_size = cmp::max(1, cmp::min(MAX_DECOMPRESS_WORKERS, spans.len()));
+        let mut buffers: Vec<Vec<u8>> = Vec::with_capacity(spans.len());
+
+        // Cap the number of OS threads in flight at `pool_size`, independent of how large this
+        // particular batch is, by working through it one bounded-size wave at a time instead of
+        // spawning one thread per chunk.
+        for batch in spans.chunks(pool_size) {
+            let results = std::thread::scope(|scope| {
+                let handles: Vec<_> = batch
+                    .iter()
+                    .map(|&(chunk, offset_merged, end_merged)| {
+                        scope.spawn(move || {
+                            self.decompress_span_with_dedup(
+                                chunk,
+                                offset_merged,
+                                end_merged,
+                                c_buf,
+                                dedup,
+                            )
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|h| h.join().expect("decompress worker thread panicked"))
+                    .collect::<Result<Vec<Vec<u8>>>>()
+            })?;
+            buffers.extend(results);
+        }
+
+        Ok(buffers)
+    }
+
     /// Decompress chunk data.
     fn decompress_chunk_data(
         &self,
@@ -360,6 +786,29 @@ pub trait BlobCache: Send + Sync {
     }
 }
 
+/// Trait representing a shared, content-addressed cache of chunk data, used to dedup identical
+/// chunks that appear in multiple blobs (common across container image layers).
+///
+/// Entries are reference-counted per referencing blob: a chunk fetched on behalf of one blob
+/// stays valid for lookups from any other blob until every referencing blob has released it
+/// (via `release_blob()`), at which point it becomes eligible for garbage collection.
+/// Implementations track, per digest, the set of `blob_id`s that currently hold a reference, so
+/// that repeated `get()`/`insert()` calls from the same blob (e.g. once per read) don't inflate
+/// the count beyond "one reference per distinct referencing blob".
+pub trait BlobChunkDedupCache: Send + Sync {
+    /// Look up cached, decompressed chunk data by content digest, adding a reference on behalf
+    /// of `blob_id` if it doesn't already hold one.
+    fn get(&self, blob_id: &str, digest: &digest::RafsDigest) -> Option<Arc<Vec<u8>>>;
+
+    /// Insert decompressed chunk data into the dedup cache, owning the first reference on behalf
+    /// of `blob_id`.
+    fn insert(&self, blob_id: &str, digest: digest::RafsDigest, data: Vec<u8>);
+
+    /// Release every reference `blob_id` holds in this cache, e.g. because that blob is being
+    /// torn down. Each entry is evicted once no blob holds a reference to it.
+    fn release_blob(&self, blob_id: &str);
+}
+
 /// Trait representing blob manager to manage a group of [BlobCache](trait.BlobCache.html) objects.
 ///
 /// The main responsibility of the blob cache manager is to create blob cache objects for blobs,
@@ -373,6 +822,11 @@ pub(crate) trait BlobCacheMgr: Send + Sync {
 
     /// Garbage-collect unused resources.
     ///
+    /// Implementations that enable the dedup cache (`dedup_cache()` returns `Some` on their
+    /// `BlobCache` objects) must call `release_dedup_chunks(id)` as part of tearing down the
+    /// blob identified by `id`, so the dedup cache's reference count for that blob's chunks is
+    /// released; otherwise shared chunks leak forever. See `release_dedup_chunks()`.
+    ///
     /// Return true if the blob cache manager itself should be garbage-collected.
     fn gc(&self, _id: Option<&str>) -> bool;
 
@@ -384,6 +838,30 @@ pub(crate) trait BlobCacheMgr: Send + Sync {
 
     /// Check the blob cache data status, if data all ready stop prefetch workers.
     fn check_stat(&self);
+
+    /// Get the shared bandwidth limiter applied to background prefetch reads of all
+    /// `BlobCache` objects managed by this manager, if configured.
+    fn prefetch_limiter(&self) -> Option<&Arc<BandwidthLimiter>> {
+        None
+    }
+
+    /// Get the shared, content-addressed chunk dedup cache used by all `BlobCache` objects
+    /// managed by this manager, if cross-blob chunk deduplication is enabled. `BlobCache`
+    /// implementations should override their own `dedup_cache()` to return this same instance,
+    /// so every blob dedups against the same cache.
+    fn dedup_cache(&self) -> Option<&Arc<dyn BlobChunkDedupCache>> {
+        None
+    }
+
+    /// Release this manager's references to shared, content-addressed dedup cache entries for
+    /// a blob that's being torn down. Implementations of `gc()` that enable the dedup cache must
+    /// call this as part of tearing down blob `id`; the backing storage for a shared chunk is
+    /// only reclaimed once its reference count drops to zero.
+    fn release_dedup_chunks(&self, id: Option<&str>) {
+        if let (Some(id), Some(dedup)) = (id, self.dedup_cache()) {
+            dedup.release_blob(id);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -506,4 +984,246 @@ mod tests {
         assert!(desc1.is_continuous(&desc2, 0));
         assert!(!desc1.is_continuous(&desc3, 0));
     }
+
+    #[test]
+    fn test_should_parallelize_decompress() {
+        // Below both thresholds: stay serial.
+        assert!(!should_parallelize_decompress(1, 0x1000));
+        // Chunk count threshold met.
+        assert!(should_parallelize_decompress(
+            PARALLEL_DECOMPRESS_CHUNK_THRESHOLD,
+            0
+        ));
+        assert!(!should_parallelize_decompress(
+            PARALLEL_DECOMPRESS_CHUNK_THRESHOLD - 1,
+            0
+        ));
+        // Total size threshold met.
+        assert!(should_parallelize_decompress(
+            1,
+            PARALLEL_DECOMPRESS_SIZE_THRESHOLD
+        ));
+        assert!(!should_parallelize_decompress(
+            1,
+            PARALLEL_DECOMPRESS_SIZE_THRESHOLD - 1
+        ));
+    }
+
+    #[test]
+    fn test_initial_backoff_delay_clamped_to_max() {
+        // A misconfigured base_delay_ms > max_delay_ms must not exceed max_delay_ms on the
+        // very first retry.
+        let policy = BackendRetryPolicy {
+            max_retries: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 100,
+        };
+        assert_eq!(initial_backoff_delay(&policy), 100);
+
+        let policy = BackendRetryPolicy {
+            max_retries: 3,
+            base_delay_ms: 50,
+            max_delay_ms: 100,
+        };
+        assert_eq!(initial_backoff_delay(&policy), 50);
+    }
+
+    #[test]
+    fn test_next_backoff_delay_doubles_and_caps() {
+        assert_eq!(next_backoff_delay(50, 1000), 100);
+        assert_eq!(next_backoff_delay(600, 1000), 1000);
+        // saturating_mul must not panic or wrap on an already-huge delay.
+        assert_eq!(next_backoff_delay(u64::MAX, 1000), 1000);
+    }
+
+    #[test]
+    fn test_backoff_sleep_ms_never_exceeds_max_delay() {
+        // delay_ms is already at the cap, so any jitter on top must be absorbed by the clamp
+        // rather than pushing the sleep past max_delay_ms.
+        for attempt in 0..=255u8 {
+            assert!(backoff_sleep_ms(1000, attempt, 1000) <= 1000);
+        }
+        // Same invariant away from the cap, where delay_ms + jitter can still overshoot it.
+        for attempt in 0..=255u8 {
+            assert!(backoff_sleep_ms(950, attempt, 1000) <= 1000);
+        }
+        // saturating_add must not panic or wrap on an already-huge delay.
+        assert_eq!(backoff_sleep_ms(u64::MAX, 3, 1000), 1000);
+    }
+
+    #[test]
+    fn test_bandwidth_limiter_rate_zero_is_noop() {
+        let limiter = BandwidthLimiter::new(0, 0);
+        let start = Instant::now();
+        limiter.acquire(1024 * 1024 * 1024);
+        assert!(Instant::now().duration_since(start) < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_bandwidth_limiter_burst_within_capacity_does_not_block() {
+        let limiter = BandwidthLimiter::new(1024, 4096);
+        let start = Instant::now();
+        limiter.acquire(4096);
+        assert!(Instant::now().duration_since(start) < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_bandwidth_limiter_zero_burst_still_throttles() {
+        // A zero-capacity bucket must not silently let every request through; it should
+        // strictly pace requests at `rate` bytes/sec instead.
+        let limiter = BandwidthLimiter::new(1_000_000, 0);
+        let start = Instant::now();
+        limiter.acquire(100_000);
+        let elapsed = Instant::now().duration_since(start);
+        assert!(elapsed >= Duration::from_millis(90));
+    }
+
+    /// Minimal `BlobCache` stub exercising only what `amplify_io_range()` (a provided trait
+    /// method) actually calls: `get_chunk_info()` and `user_io_batch_size()`. The simple flag
+    /// getters (`is_legacy_stargz()`, `need_validate()`, `is_prefetch_active()`) return `false`;
+    /// every other method panics if hit, since amplify_io_range() never calls them.
+    struct TestCache {
+        chunks: Vec<Arc<dyn BlobChunkInfo>>,
+        batch_size: u64,
+    }
+
+    impl BlobCache for TestCache {
+        fn blob_id(&self) -> &str {
+            unimplemented!()
+        }
+
+        fn blob_uncompressed_size(&self) -> Result<u64> {
+            unimplemented!()
+        }
+
+        fn blob_compressed_size(&self) -> Result<u64> {
+            unimplemented!()
+        }
+
+        fn compressor(&self) -> compress::Algorithm {
+            unimplemented!()
+        }
+
+        fn digester(&self) -> digest::Algorithm {
+            unimplemented!()
+        }
+
+        fn is_legacy_stargz(&self) -> bool {
+            false
+        }
+
+        fn need_validate(&self) -> bool {
+            false
+        }
+
+        fn reader(&self) -> &dyn BlobReader {
+            unimplemented!()
+        }
+
+        fn get_chunk_map(&self) -> &Arc<dyn ChunkMap> {
+            unimplemented!()
+        }
+
+        fn get_chunk_info(&self, chunk_index: u32) -> Option<Arc<dyn BlobChunkInfo>> {
+            self.chunks.iter().find(|c| c.id() == chunk_index).cloned()
+        }
+
+        fn start_prefetch(&self) -> StorageResult<()> {
+            unimplemented!()
+        }
+
+        fn stop_prefetch(&self) -> StorageResult<()> {
+            unimplemented!()
+        }
+
+        fn is_prefetch_active(&self) -> bool {
+            false
+        }
+
+        fn prefetch(
+            &self,
+            _cache: Arc<dyn BlobCache>,
+            _prefetches: &[BlobPrefetchRequest],
+            _bios: &[BlobIoDesc],
+        ) -> StorageResult<usize> {
+            unimplemented!()
+        }
+
+        fn read(&self, _iovec: &mut BlobIoVec, _buffers: &[FileVolatileSlice]) -> Result<usize> {
+            unimplemented!()
+        }
+
+        fn user_io_batch_size(&self) -> u64 {
+            self.batch_size
+        }
+    }
+
+    fn mock_chunk(
+        index: u32,
+        compress_offset: u64,
+        compress_size: u32,
+        uncompress_size: u32,
+    ) -> Arc<dyn BlobChunkInfo> {
+        Arc::new(MockChunkInfo {
+            block_id: Default::default(),
+            blob_index: 1,
+            flags: BlobChunkFlags::empty(),
+            compress_size,
+            uncompress_size,
+            compress_offset,
+            uncompress_offset: compress_offset,
+            file_offset: compress_offset,
+            index,
+            reserved: 0,
+        }) as Arc<dyn BlobChunkInfo>
+    }
+
+    #[test]
+    fn test_amplify_io_range_disabled_when_batch_size_zero() {
+        let chunk0 = mock_chunk(0, 0, 0x800, 0x1000);
+        let cache = TestCache {
+            chunks: vec![chunk0.clone()],
+            batch_size: 0,
+        };
+
+        let (offset, size, chunks) = cache.amplify_io_range(0, 0x800, &[chunk0]);
+        assert_eq!(offset, 0);
+        assert_eq!(size, 0x800);
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_amplify_io_range_pulls_in_contiguous_chunks() {
+        let chunk0 = mock_chunk(0, 0, 0x800, 0x1000);
+        let chunk1 = mock_chunk(1, 0x800, 0x800, 0x1000);
+        let chunk2 = mock_chunk(2, 0x1000, 0x800, 0x1000);
+        let cache = TestCache {
+            chunks: vec![chunk0.clone(), chunk1, chunk2],
+            batch_size: 0x1800,
+        };
+
+        let (offset, size, amplified) = cache.amplify_io_range(0, 0x800, &[chunk0]);
+        assert_eq!(offset, 0);
+        assert_eq!(size, 0x1800);
+        assert_eq!(amplified.len(), 3);
+        assert_eq!(amplified[1].id(), 1);
+        assert_eq!(amplified[2].id(), 2);
+    }
+
+    #[test]
+    fn test_amplify_io_range_stops_at_noncontiguous_chunk() {
+        let chunk0 = mock_chunk(0, 0, 0x800, 0x1000);
+        // chunk1 exists but its compressed_offset leaves a gap after chunk0, so it must not
+        // be amplified in even though it's within the batch size budget.
+        let chunk1 = mock_chunk(1, 0x900, 0x800, 0x1000);
+        let cache = TestCache {
+            chunks: vec![chunk0.clone(), chunk1],
+            batch_size: 0x2000,
+        };
+
+        let (offset, size, amplified) = cache.amplify_io_range(0, 0x800, &[chunk0]);
+        assert_eq!(offset, 0);
+        assert_eq!(size, 0x800);
+        assert_eq!(amplified.len(), 1);
+    }
 }